@@ -1,5 +1,5 @@
 use ::alloc::{alloc, vec};
-use core::{cell, cmp, ops, ptr};
+use core::{cell, cmp, iter, mem, ops, ptr};
 
 /// A collection onto which new values can be appended, while still keeping references to previous
 /// values valid.
@@ -59,17 +59,40 @@ impl<T> AppendOnlyVec<T> {
     /// Consumes a `T`, appends it to the end of the vector, and returns a reference to the newly
     /// appended element.
     pub fn push(&self, value: T) -> &T {
+        self.try_push(value)
+            .unwrap_or_else(|(_, error)| panic!("{}", error))
+    }
+
+    /// Like [`push`](Self::push), but returns an error instead of panicking when a fresh segment
+    /// cannot be allocated.
+    ///
+    /// On failure the moved value is handed back alongside the error so that nothing is lost.
+    pub fn try_push(&self, value: T) -> Result<&T, (T, crate::TryReserveError)> {
         unsafe {
             let tail = self.tail.get();
             if (*tail).is_null() {
-                ptr::write(tail, alloc::alloc(self.layout) as *mut T)
+                let segment = alloc::alloc(self.layout) as *mut T;
+                if segment.is_null() {
+                    return Err((value, crate::TryReserveError::alloc_error()));
+                }
+                ptr::write(tail, segment)
             }
 
             let tail_size = self.tail_size.get();
+            let next_tail_size = tail_size + 1;
+
+            // When this push fills the segment we have to archive the tail into `segments`. Reserve
+            // room for it before the value is committed, so a reservation failure loses nothing.
+            if next_tail_size == SEGMENT_CAPACITY {
+                let segments = &mut *self.segments.get();
+                if segments.try_reserve(1).is_err() {
+                    return Err((value, crate::TryReserveError::alloc_error()));
+                }
+            }
+
             let dst = (*tail).add(tail_size);
             ptr::write(dst, value);
 
-            let next_tail_size = tail_size + 1;
             self.tail_size.set(if next_tail_size == SEGMENT_CAPACITY {
                 let tail = ptr::replace(tail, ptr::null_mut());
                 (*self.segments.get()).push(tail);
@@ -78,7 +101,7 @@ impl<T> AppendOnlyVec<T> {
                 next_tail_size
             });
 
-            &*dst
+            Ok(&*dst)
         }
     }
 
@@ -90,6 +113,269 @@ impl<T> AppendOnlyVec<T> {
     pub fn is_empty(&self) -> bool {
         unsafe { self.tail_size.get() == 0 && (*self.segments.get()).is_empty() }
     }
+
+    /// Returns an iterator borrowing each element in insertion order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        unsafe {
+            Iter {
+                segments: (*self.segments.get()).as_slice(),
+                tail: *self.tail.get(),
+                segment: 0,
+                offset: 0,
+                remaining: self.len(),
+            }
+        }
+    }
+}
+
+/// A borrowing iterator over the elements of an [`AppendOnlyVec`].
+///
+/// It walks each full segment in turn, yielding `SEGMENT_CAPACITY` elements before moving on, then
+/// yields the partially filled `tail`, mirroring the layout of the `Index` impl.
+pub struct Iter<'a, T> {
+    segments: &'a [*mut T],
+    tail: *mut T,
+    segment: usize,
+    offset: usize,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        unsafe {
+            let item = if self.segment < self.segments.len() {
+                let value = &*(*self.segments.get_unchecked(self.segment)).add(self.offset);
+                self.offset += 1;
+                if self.offset == SEGMENT_CAPACITY {
+                    self.segment += 1;
+                    self.offset = 0;
+                }
+                value
+            } else {
+                let value = &*self.tail.add(self.offset);
+                self.offset += 1;
+                value
+            };
+            self.remaining -= 1;
+            Some(item)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> IntoIterator for &'a AppendOnlyVec<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// A consuming iterator over the elements of an [`AppendOnlyVec`], created by `into_iter`.
+pub struct IntoIter<T> {
+    segments: vec::Vec<*mut T>,
+    tail: *mut T,
+    layout: alloc::Layout,
+    segment: usize,
+    offset: usize,
+    remaining: usize,
+}
+
+impl<T> IntoIterator for AppendOnlyVec<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        // Take ownership of the buffers without running `AppendOnlyVec`'s `Drop`: `IntoIter` is now
+        // responsible for dropping the remaining elements and deallocating the segments.
+        let me = mem::ManuallyDrop::new(self);
+        unsafe {
+            let segments = ptr::read(me.segments.get());
+            let remaining = segments.len() * SEGMENT_CAPACITY + me.tail_size.get();
+            IntoIter {
+                segments,
+                tail: *me.tail.get(),
+                layout: me.layout,
+                segment: 0,
+                offset: 0,
+                remaining,
+            }
+        }
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        unsafe {
+            let value = if self.segment < self.segments.len() {
+                let value = ptr::read((*self.segments.get_unchecked(self.segment)).add(self.offset));
+                self.offset += 1;
+                if self.offset == SEGMENT_CAPACITY {
+                    self.segment += 1;
+                    self.offset = 0;
+                }
+                value
+            } else {
+                let value = ptr::read(self.tail.add(self.offset));
+                self.offset += 1;
+                value
+            };
+            self.remaining -= 1;
+            Some(value)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> Drop for IntoIter<T> {
+    fn drop(&mut self) {
+        unsafe {
+            // Drop the elements that have not been yielded yet…
+            while self.next().is_some() {}
+            // …then release every buffer, just like `AppendOnlyVec::drop`.
+            for &segment in &self.segments {
+                alloc::dealloc(segment as _, self.layout);
+            }
+            if !self.tail.is_null() {
+                alloc::dealloc(self.tail as _, self.layout);
+            }
+        }
+    }
+}
+
+impl<T> iter::FromIterator<T> for AppendOnlyVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let vec = AppendOnlyVec::new();
+        for value in iter {
+            vec.push(value);
+        }
+        vec
+    }
+}
+
+impl<T> Extend<T> for AppendOnlyVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// An [`AppendOnlyVec`] that keeps its first `N` elements in an inline buffer and only starts
+/// allocating heap segments once it grows beyond `N`.
+///
+/// For short-lived buffers that usually hold only a handful of elements, this avoids paying for a
+/// full [`SEGMENT_CAPACITY`]-element segment on the first `push`. References returned by `push`
+/// stay valid forever: the inline buffer lives behind the shared `&self` and is never reallocated,
+/// and the overflow segments have the same stability guarantee as `AppendOnlyVec`.
+///
+/// # Example
+///
+/// ```
+/// # use cursed_collections::SmallAppendOnlyVec;
+/// let vec = SmallAppendOnlyVec::<String, 2>::new();
+/// let first = vec.push("hello".into());
+/// let second = vec.push("bye".into());
+/// // This one spills past the inline buffer onto the heap, yet the earlier references are fine.
+/// let third = vec.push("again".into());
+/// assert_eq!((first, second, third), (&"hello".to_string(), &"bye".to_string(), &"again".to_string()));
+/// ```
+pub struct SmallAppendOnlyVec<T, const N: usize> {
+    inline: cell::UnsafeCell<[mem::MaybeUninit<T>; N]>,
+    inline_size: cell::Cell<usize>,
+    overflow: AppendOnlyVec<T>,
+}
+
+impl<T, const N: usize> SmallAppendOnlyVec<T, N> {
+    /// Creates an empty `SmallAppendOnlyVec`.
+    pub fn new() -> Self {
+        Self {
+            inline: cell::UnsafeCell::new([const { mem::MaybeUninit::uninit() }; N]),
+            inline_size: cell::Cell::new(0),
+            overflow: AppendOnlyVec::new(),
+        }
+    }
+
+    /// Consumes a `T`, appends it to the end of the vector, and returns a reference to the newly
+    /// appended element.
+    pub fn push(&self, value: T) -> &T {
+        let inline_size = self.inline_size.get();
+        if inline_size < N {
+            unsafe {
+                let slot = self
+                    .inline
+                    .get()
+                    .cast::<mem::MaybeUninit<T>>()
+                    .add(inline_size);
+                (*slot).write(value);
+                self.inline_size.set(inline_size + 1);
+                &*(*slot).as_ptr()
+            }
+        } else {
+            self.overflow.push(value)
+        }
+    }
+
+    /// Returns the number of elements in the vector.
+    pub fn len(&self) -> usize {
+        self.inline_size.get() + self.overflow.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T, const N: usize> Drop for SmallAppendOnlyVec<T, N> {
+    fn drop(&mut self) {
+        unsafe {
+            let base = self.inline.get().cast::<mem::MaybeUninit<T>>();
+            for i in 0..self.inline_size.get() {
+                ptr::drop_in_place((*base.add(i)).as_mut_ptr());
+            }
+            // `overflow` drops itself, releasing any heap segments.
+        }
+    }
+}
+
+impl<T, const N: usize> Default for SmallAppendOnlyVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> ops::Index<usize> for SmallAppendOnlyVec<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        if index < N {
+            assert!(index < self.inline_size.get(), "out of bounds, buddy");
+            unsafe { &*(*self.inline.get().cast::<mem::MaybeUninit<T>>().add(index)).as_ptr() }
+        } else {
+            &self.overflow[index - N]
+        }
+    }
 }
 
 impl<T> Drop for AppendOnlyVec<T> {
@@ -142,7 +428,7 @@ impl<T> ops::Index<usize> for AppendOnlyVec<T> {
 
 #[cfg(test)]
 mod tests {
-    use super::{AppendOnlyVec, SEGMENT_CAPACITY};
+    use super::{AppendOnlyVec, SmallAppendOnlyVec, SEGMENT_CAPACITY};
     use quickcheck_macros::quickcheck;
     use std::ptr;
 
@@ -212,6 +498,79 @@ mod tests {
         assert_eq!(33, vec.len())
     }
 
+    #[test]
+    fn iter_yields_every_element_in_order() {
+        let vec = AppendOnlyVec::<String>::new();
+        for i in 0..(SEGMENT_CAPACITY + 1) {
+            vec.push(format!("{}", i));
+        }
+        let collected = vec.iter().cloned().collect::<Vec<_>>();
+        assert_eq!(SEGMENT_CAPACITY + 1, collected.len());
+        assert!((0..(SEGMENT_CAPACITY + 1)).all(|i| collected[i] == format!("{}", i)));
+    }
+
+    #[test]
+    fn try_push_returns_the_stored_reference() {
+        let vec = AppendOnlyVec::<String>::new();
+        assert_eq!(vec.try_push("hello".into()).unwrap(), "hello");
+        assert_eq!(1, vec.len());
+    }
+
+    #[test]
+    fn iter_is_empty_for_empty_vec() {
+        let vec = AppendOnlyVec::<String>::new();
+        assert_eq!(0, vec.iter().count());
+    }
+
+    #[test]
+    fn into_iter_yields_owned_values() {
+        let vec = AppendOnlyVec::<String>::new();
+        for i in 0..(SEGMENT_CAPACITY + 3) {
+            vec.push(format!("{}", i));
+        }
+        let collected = vec.into_iter().collect::<Vec<_>>();
+        assert!((0..(SEGMENT_CAPACITY + 3)).all(|i| collected[i] == format!("{}", i)));
+    }
+
+    #[test]
+    fn from_iter_and_extend_round_trip() {
+        let mut vec = (0..3).map(|i| format!("{}", i)).collect::<AppendOnlyVec<_>>();
+        vec.extend((3..5).map(|i| format!("{}", i)));
+        assert_eq!(5, vec.len());
+        assert_eq!(vec[4], "4");
+    }
+
+    #[test]
+    fn small_stays_inline_for_the_first_n_elements() {
+        let vec = SmallAppendOnlyVec::<String, 2>::new();
+        vec.push("hello".into());
+        vec.push("bye".into());
+        assert_eq!(2, vec.len());
+        assert_eq!(vec[0], "hello");
+        assert_eq!(vec[1], "bye");
+    }
+
+    #[test]
+    fn small_references_still_valid_after_spilling_to_the_heap() {
+        let vec = SmallAppendOnlyVec::<String, 2>::new();
+        let mut references = Vec::<&String>::new();
+        for i in 0..(SEGMENT_CAPACITY + 3) {
+            references.push(vec.push(format!("{}", i)));
+        }
+
+        assert_eq!(SEGMENT_CAPACITY + 3, vec.len());
+        assert!(ptr::eq(&vec[0], references[0]));
+        assert!((0..(SEGMENT_CAPACITY + 3)).all(|i| references[i].as_str() == format!("{}", i)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn small_index_out_of_bounds() {
+        let vec = SmallAppendOnlyVec::<String, 2>::new();
+        vec.push("hello".into());
+        let _ = &vec[1];
+    }
+
     #[quickcheck]
     #[cfg_attr(miri, ignore)]
     fn is_same_as_vector_once_fully_initialized(expected: Vec<String>) -> bool {
@@ -1,5 +1,5 @@
 use ::alloc::alloc;
-use core::{mem, ptr, slice};
+use core::{cell, mem, ptr, slice};
 
 /// A collection with a size defined at creation, but where entries are initialized later.
 ///
@@ -33,9 +33,20 @@ pub struct LazyArray<T> {
 
 impl<T> LazyArray<T> {
     pub fn new(capacity: usize) -> Self {
+        Self::try_new(capacity).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like [`new`](Self::new), but returns an error instead of panicking when the backing buffer
+    /// cannot be allocated, either because the capacity overflows a `Layout` or because the
+    /// allocator returns null.
+    pub fn try_new(capacity: usize) -> Result<Self, crate::TryReserveError> {
         unsafe {
-            let layout = alloc::Layout::array::<Option<T>>(capacity).expect("size overflow");
+            let layout = alloc::Layout::array::<Option<T>>(capacity)
+                .map_err(|_| crate::TryReserveError::capacity_overflow())?;
             let buffer = alloc::alloc(layout);
+            if buffer.is_null() {
+                return Err(crate::TryReserveError::alloc_error());
+            }
             {
                 let slice =
                     slice::from_raw_parts_mut(buffer as *mut mem::MaybeUninit<Option<T>>, capacity);
@@ -43,11 +54,11 @@ impl<T> LazyArray<T> {
                     *i = mem::MaybeUninit::new(None);
                 }
             }
-            Self {
+            Ok(Self {
                 buffer: buffer as *mut Option<T>,
                 capacity,
                 layout,
-            }
+            })
         }
     }
 
@@ -93,9 +104,71 @@ impl<T> Default for LazyArray<T> {
     }
 }
 
+/// A [`LazyArray`] whose capacity is fixed at compile time and stored inline, with no heap
+/// allocation.
+///
+/// Because the storage lives directly inside the value, an `InlineLazyArray` can be placed on the
+/// stack, making it usable in embedded/`no_std` contexts where a heap may not be available. The
+/// [`get`](Self::get)/[`get_or_insert`](Self::get_or_insert) interface and its interior-mutability
+/// safety argument are identical to [`LazyArray`]: we never hand out a `&mut` into an
+/// already-initialized slot.
+///
+/// # Example
+///
+/// ```
+/// # use cursed_collections::InlineLazyArray;
+/// let array = InlineLazyArray::<i32, 4>::new();
+///
+/// assert_eq!(array.get(0), None);
+/// assert_eq!(array.get_or_insert(0, 123), &123);
+/// assert_eq!(array.get(0), Some(&123));
+/// ```
+#[derive(Debug)]
+pub struct InlineLazyArray<T, const N: usize> {
+    buffer: [cell::UnsafeCell<Option<T>>; N],
+}
+
+impl<T, const N: usize> InlineLazyArray<T, N> {
+    /// Creates an `InlineLazyArray` with every slot empty.
+    ///
+    /// This is a `const fn` so that instances can be built in const contexts.
+    pub const fn new() -> Self {
+        Self {
+            buffer: [const { cell::UnsafeCell::new(None) }; N],
+        }
+    }
+
+    pub fn get_or_insert(&self, index: usize, t: T) -> &T {
+        assert!(index < N);
+        unsafe {
+            // Same reasoning as `LazyArray::get_or_insert`: we must not build a `&mut` into a slot
+            // that may already be borrowed through a shared reference.
+            let entry = self.buffer[index].get();
+            match *entry {
+                None => {
+                    ptr::write(entry, Some(t));
+                    (*entry).as_ref().unwrap_unchecked()
+                }
+                Some(ref v) => v,
+            }
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        assert!(index < N);
+        unsafe { (*self.buffer[index].get()).as_ref() }
+    }
+}
+
+impl<T, const N: usize> Default for InlineLazyArray<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::LazyArray;
+    use super::{InlineLazyArray, LazyArray};
 
     #[test]
     fn it_works() {
@@ -111,6 +184,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn try_new_succeeds_for_a_reasonable_capacity() {
+        let lazy_array = LazyArray::<i32>::try_new(10).unwrap();
+        assert_eq!(lazy_array.get(7), None);
+    }
+
+    #[test]
+    fn try_new_reports_capacity_overflow() {
+        assert!(LazyArray::<i32>::try_new(usize::MAX).is_err());
+    }
+
     #[test]
     fn cannot_insert_twice() {
         let lazy_array = LazyArray::<i32>::new(10);
@@ -124,4 +208,32 @@ mod tests {
         let lazy_array = LazyArray::<i32>::new(10);
         lazy_array.get_or_insert(10, 112233);
     }
+
+    #[test]
+    fn inline_it_works() {
+        let lazy_array = InlineLazyArray::<i32, 10>::new();
+        for i in 0..10 {
+            assert_eq!(lazy_array.get(i), None)
+        }
+
+        assert_eq!(lazy_array.get_or_insert(7, 112233), &112233);
+
+        for i in 0..10 {
+            assert_eq!(lazy_array.get(i), if i == 7 { Some(&112233) } else { None })
+        }
+    }
+
+    #[test]
+    fn inline_cannot_insert_twice() {
+        let lazy_array = InlineLazyArray::<i32, 10>::new();
+        assert_eq!(lazy_array.get_or_insert(7, 112233), &112233);
+        assert_eq!(lazy_array.get_or_insert(7, 445566), &112233);
+    }
+
+    #[test]
+    #[should_panic]
+    fn inline_cannot_put_out_of_bounds() {
+        let lazy_array = InlineLazyArray::<i32, 10>::new();
+        lazy_array.get_or_insert(10, 112233);
+    }
 }
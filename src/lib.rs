@@ -12,6 +12,10 @@
 
 mod append_only_vec;
 mod lazy_array;
+mod symbol_table;
+mod try_reserve_error;
 
-pub use crate::append_only_vec::AppendOnlyVec;
-pub use crate::lazy_array::LazyArray;
+pub use crate::append_only_vec::{AppendOnlyVec, SmallAppendOnlyVec};
+pub use crate::lazy_array::{InlineLazyArray, LazyArray};
+pub use crate::symbol_table::{Symbol, SymbolId, SymbolTable};
+pub use crate::try_reserve_error::TryReserveError;
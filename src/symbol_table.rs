@@ -1,7 +1,7 @@
 use ::alloc::{alloc, string::String, vec};
 use core::borrow::Borrow;
 use core::{cell, fmt, hash, marker, mem, ptr, slice, str};
-use hashbrown::HashSet;
+use hashbrown::HashMap;
 
 const LARGE_SYMBOL_THRESHOLD: usize = 1 << 9;
 const SEGMENT_CAPACITY: usize = 1 << 12;
@@ -49,6 +49,22 @@ impl<'table> Symbol<'table> {
     }
 }
 
+/// A dense, 4-byte handle identifying a symbol interned in a [`SymbolTable`].
+///
+/// Unlike [`Symbol`], which is a two-word fat pointer, a `SymbolId` is a small copyable integer — its value
+/// is the order in which the symbol was first interned. This makes it convenient to store in AST
+/// nodes and to serialize. Resolve it back to its text with [`SymbolTable::resolve`].
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SymbolId(u32);
+
+impl SymbolId {
+    /// The underlying integer, i.e. the order in which the symbol was first interned.
+    pub fn to_u32(self) -> u32 {
+        self.0
+    }
+}
+
 impl<'table> PartialEq for Symbol<'table> {
     fn eq(&self, other: &Self) -> bool {
         ptr::eq(self.ptr, other.ptr)
@@ -86,7 +102,8 @@ const BUFFER_LAYOUT: alloc::Layout = alloc::Layout::new::<[u8; SEGMENT_CAPACITY]
 /// A set of strings. Unlike a regular set, strings are stored contiguously in pages to reduce
 /// memory usage.
 pub struct SymbolTable {
-    lookup: cell::UnsafeCell<HashSet<SymbolKey>>,
+    lookup: cell::UnsafeCell<HashMap<SymbolKey, SymbolId>>,
+    symbols: cell::UnsafeCell<vec::Vec<*const str>>,
     small_symbols: cell::UnsafeCell<vec::Vec<*const u8>>,
     large_symbols: cell::UnsafeCell<vec::Vec<(*const u8, usize, usize)>>,
     tail: cell::Cell<*mut u8>,
@@ -100,7 +117,8 @@ impl SymbolTable {
     pub fn new() -> Self {
         unsafe {
             Self {
-                lookup: cell::UnsafeCell::new(HashSet::new()),
+                lookup: cell::UnsafeCell::new(HashMap::new()),
+                symbols: cell::UnsafeCell::new(vec![]),
                 small_symbols: cell::UnsafeCell::new(vec![]),
                 large_symbols: cell::UnsafeCell::new(vec![]),
                 tail: cell::Cell::new(alloc::alloc(BUFFER_LAYOUT)),
@@ -119,15 +137,92 @@ impl SymbolTable {
     /// assert_eq!(table.intern("my symbol"), table.intern("my symbol"));
     /// ```
     pub fn intern(&self, text: impl Into<String> + AsRef<str>) -> Symbol {
+        self.try_intern(text)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like [`intern`](Self::intern), but returns an error instead of panicking when a fresh segment
+    /// or a bookkeeping allocation cannot be made.
+    ///
+    /// As with [`try_gensym`](Self::try_gensym), interning a *large* symbol still allocates a
+    /// backing `String`, which aborts on allocation failure.
+    pub fn try_intern(
+        &self,
+        text: impl Into<String> + AsRef<str>,
+    ) -> Result<Symbol<'_>, crate::TryReserveError> {
         unsafe {
             let lookup = &mut *self.lookup.get();
-            if let Some(&SymbolKey(ptr)) = lookup.get(&SymbolKey(text.as_ref())) {
-                return Symbol::new(ptr);
+            if let Some((&SymbolKey(ptr), _)) = lookup.get_key_value(&SymbolKey(text.as_ref())) {
+                return Ok(Symbol::new(ptr));
+            }
+
+            // Reserve room for the new entry up front so that, once the symbol has been created,
+            // the bookkeeping below cannot fail and leave a half-registered symbol behind.
+            lookup
+                .try_reserve(1)
+                .map_err(|_| crate::TryReserveError::alloc_error())?;
+            {
+                let symbols = &mut *self.symbols.get();
+                symbols
+                    .try_reserve(1)
+                    .map_err(|_| crate::TryReserveError::alloc_error())?;
             }
 
-            let symbol @ Symbol { ptr, .. } = self.gensym(text);
-            lookup.insert(SymbolKey(ptr));
-            symbol
+            let symbol @ Symbol { ptr, .. } = self.try_gensym(text)?;
+            let symbols = &mut *self.symbols.get();
+            let id = SymbolId(symbols.len() as u32);
+            symbols.push(ptr);
+            lookup.insert(SymbolKey(ptr), id);
+            Ok(symbol)
+        }
+    }
+
+    /// Interns `text` like [`intern`](Self::intern), returning its dense [`SymbolId`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cursed_collections::SymbolTable;
+    /// let table = SymbolTable::new();
+    /// let id = table.intern_id("my symbol");
+    /// assert_eq!(table.intern_id("my symbol"), id);
+    /// assert_eq!(table.resolve(id), "my symbol");
+    /// ```
+    pub fn intern_id(&self, text: impl Into<String> + AsRef<str>) -> SymbolId {
+        self.symbol_id(self.intern(text))
+    }
+
+    /// Returns the [`SymbolId`] of an interned `symbol`.
+    ///
+    /// The symbol must have been produced by [`intern`](Self::intern) or
+    /// [`intern_id`](Self::intern_id) on this table.
+    pub fn symbol_id(&self, symbol: Symbol) -> SymbolId {
+        unsafe {
+            let lookup = &*self.lookup.get();
+            let id = lookup[&SymbolKey(symbol.ptr)];
+            let symbols = &*self.symbols.get();
+            debug_assert!(
+                ptr::eq(symbols[id.0 as usize], symbol.ptr),
+                "symbol was not interned in this table",
+            );
+            id
+        }
+    }
+
+    /// Resolves a [`SymbolId`] back to its text.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cursed_collections::SymbolTable;
+    /// let table = SymbolTable::new();
+    /// let id = table.intern_id("laura");
+    /// assert_eq!(table.resolve(id), "laura");
+    /// ```
+    pub fn resolve(&self, id: SymbolId) -> &str {
+        unsafe {
+            let symbols = &*self.symbols.get();
+            &*symbols[id.0 as usize]
         }
     }
 
@@ -147,29 +242,58 @@ impl SymbolTable {
     /// The name "`gensym`" is common within the Lisp family of languages where symbols are built in
     /// the language itself.
     pub fn gensym(&self, text: impl Into<String> + AsRef<str>) -> Symbol {
+        self.try_gensym(text)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Like [`gensym`](Self::gensym), but returns an error instead of panicking when a fresh segment
+    /// or a bookkeeping allocation cannot be made.
+    ///
+    /// Interning a *large* symbol (one of at least `LARGE_SYMBOL_THRESHOLD` bytes) still allocates
+    /// a backing `String`, which aborts on allocation failure.
+    pub fn try_gensym(
+        &self,
+        text: impl Into<String> + AsRef<str>,
+    ) -> Result<Symbol<'_>, crate::TryReserveError> {
         unsafe {
             let text_len = text.as_ref().len();
             if text_len >= LARGE_SYMBOL_THRESHOLD {
+                let large_symbols = &mut *self.large_symbols.get();
+                large_symbols
+                    .try_reserve(1)
+                    .map_err(|_| crate::TryReserveError::alloc_error())?;
                 let large_symbol = mem::ManuallyDrop::new(text.into());
                 let ptr = large_symbol.as_ptr();
                 let size = large_symbol.len();
-                (*self.large_symbols.get()).push((ptr, size, large_symbol.capacity()));
-                return Symbol::new(str::from_utf8_unchecked(slice::from_raw_parts(ptr, size)));
+                large_symbols.push((ptr, size, large_symbol.capacity()));
+                return Ok(Symbol::new(str::from_utf8_unchecked(slice::from_raw_parts(
+                    ptr, size,
+                ))));
             }
 
             if text_len + self.tail_offset.get() > SEGMENT_CAPACITY {
+                // Reserve the archive slot and allocate the replacement segment before mutating any
+                // bookkeeping, so that a failure leaves the table untouched.
+                let small_symbols = &mut *self.small_symbols.get();
+                small_symbols
+                    .try_reserve(1)
+                    .map_err(|_| crate::TryReserveError::alloc_error())?;
+                let fresh = alloc::alloc(BUFFER_LAYOUT);
+                if fresh.is_null() {
+                    return Err(crate::TryReserveError::alloc_error());
+                }
                 self.tail_offset.set(0);
-                let prev_tail = self.tail.replace(alloc::alloc(BUFFER_LAYOUT));
-                (*self.small_symbols.get()).push(prev_tail);
+                let prev_tail = self.tail.replace(fresh);
+                small_symbols.push(prev_tail);
             }
 
             let tail_offset = self.tail_offset.get();
             let dst = self.tail.get().add(tail_offset);
             ptr::copy_nonoverlapping(text.as_ref().as_ptr(), dst, text_len);
             self.tail_offset.replace(tail_offset + text_len);
-            Symbol::new(str::from_utf8_unchecked(slice::from_raw_parts(
+            Ok(Symbol::new(str::from_utf8_unchecked(slice::from_raw_parts(
                 dst, text_len,
-            )))
+            ))))
         }
     }
 }
@@ -206,6 +330,12 @@ mod tests {
         assert_ne!(table.intern("laura"), table.intern("maddy"));
     }
 
+    #[test]
+    fn try_intern_returns_the_same_symbol_as_intern() {
+        let table = SymbolTable::new();
+        assert_eq!(table.try_intern("laura").unwrap(), table.intern("laura"));
+    }
+
     #[test]
     fn empty_symbol_is_different_from_other_symbols() {
         {
@@ -233,6 +363,25 @@ mod tests {
         assert_eq!(table.intern(&text), table.intern(text));
     }
 
+    #[test]
+    fn ids_round_trip_through_resolve() {
+        let table = SymbolTable::new();
+        let laura = table.intern_id("laura");
+        let maddy = table.intern_id("maddy");
+        assert_ne!(laura, maddy);
+        assert_eq!(table.resolve(laura), "laura");
+        assert_eq!(table.resolve(maddy), "maddy");
+    }
+
+    #[test]
+    fn ids_are_dense_and_stable() {
+        let table = SymbolTable::new();
+        assert_eq!(table.intern_id("a").to_u32(), 0);
+        assert_eq!(table.intern_id("b").to_u32(), 1);
+        assert_eq!(table.intern_id("a").to_u32(), 0);
+        assert_eq!(table.symbol_id(table.intern("b")).to_u32(), 1);
+    }
+
     #[test]
     fn interning_can_refer_to_previous_segment() {
         let table = SymbolTable::new();
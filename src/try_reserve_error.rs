@@ -0,0 +1,46 @@
+use core::fmt;
+
+/// The error returned by the `try_*` constructors and insertion methods when the underlying
+/// allocation cannot be performed.
+///
+/// Unlike the panicking methods, which panic on allocation failure, the fallible
+/// methods hand this back so that `no_std`/kernel-style callers can recover.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TryReserveError {
+    kind: TryReserveErrorKind,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum TryReserveErrorKind {
+    /// The `Layout` for the requested capacity overflowed `isize::MAX`.
+    CapacityOverflow,
+    /// The allocator returned a null pointer.
+    AllocError,
+}
+
+impl TryReserveError {
+    pub(crate) fn capacity_overflow() -> Self {
+        Self {
+            kind: TryReserveErrorKind::CapacityOverflow,
+        }
+    }
+
+    pub(crate) fn alloc_error() -> Self {
+        Self {
+            kind: TryReserveErrorKind::AllocError,
+        }
+    }
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self.kind {
+            TryReserveErrorKind::CapacityOverflow => {
+                "memory allocation failed because the computed capacity overflowed"
+            }
+            TryReserveErrorKind::AllocError => {
+                "memory allocation failed because the allocator returned an error"
+            }
+        })
+    }
+}